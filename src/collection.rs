@@ -0,0 +1,158 @@
+//! Collections let one deployment host several independent gift campaigns,
+//! each with its own transfer/metadata/supply rules, instead of every token
+//! sharing the same flat set of settings.
+
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, AccountId};
+use std::ops::BitOr;
+
+use crate::events::NftMint;
+use crate::metadata::TokenMetadata;
+use crate::{NFTContract, NFTContractContract, NFT};
+
+pub type CollectionId = u64;
+
+/// A small bitflag set describing what's allowed within a collection.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CollectionSettings(u8);
+
+impl CollectionSettings {
+    pub const NONE: CollectionSettings = CollectionSettings(0);
+    pub const TRANSFERABLE: CollectionSettings = CollectionSettings(1 << 0);
+    pub const UNLOCKED_METADATA: CollectionSettings = CollectionSettings(1 << 1);
+
+    pub fn contains(&self, flag: CollectionSettings) -> bool {
+        self.0 & flag.0 == flag.0
+    }
+}
+
+impl BitOr for CollectionSettings {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        CollectionSettings(self.0 | rhs.0)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct Collection {
+    pub admin: AccountId,
+    pub settings: CollectionSettings,
+    pub max_supply: Option<u64>,
+    pub minted: u64,
+}
+
+#[near_bindgen]
+impl NFTContract {
+    /// Creates a new gift campaign. Only the contract owner may open one;
+    /// `admin` is then the account allowed to mint into it.
+    pub fn create_collection(
+        &mut self,
+        admin: AccountId,
+        settings: CollectionSettings,
+        max_supply: Option<u64>,
+    ) -> CollectionId {
+        self.require_owner();
+
+        let collection_id = self.next_collection_id;
+        self.next_collection_id += 1;
+        self.collections.insert(
+            &collection_id,
+            &Collection {
+                admin,
+                settings,
+                max_supply,
+                minted: 0,
+            },
+        );
+        collection_id
+    }
+
+    /// Mints a token into `collection_id`, rejecting once `max_supply` (if
+    /// any) has been reached.
+    pub fn mint_into(&mut self, collection_id: CollectionId, metadata: Option<TokenMetadata>) -> u64 {
+        let mut collection = match self.collections.get(&collection_id) {
+            Some(collection) => collection,
+            None => env::panic(b"Collection not found"),
+        };
+        if env::predecessor_account_id() != collection.admin {
+            env::panic(b"Only the collection admin can mint into it.");
+        }
+        if let Some(max_supply) = collection.max_supply {
+            if collection.minted >= max_supply {
+                env::panic(b"Collection has reached its max supply.");
+            }
+        }
+
+        let token_id = self.next_public_token_id;
+        self.next_public_token_id += 1;
+        self.nfts.insert(
+            &token_id,
+            &NFT {
+                owner_id: collection.admin.clone(),
+                token_id,
+                metadata: metadata.unwrap_or_default(),
+                uses: None,
+            },
+        );
+        self.token_ids.insert(&token_id);
+        self.internal_add_token_to_owner(&collection.admin.clone(), token_id);
+        self.token_collection.insert(&token_id, &collection_id);
+
+        collection.minted += 1;
+        self.collections.insert(&collection_id, &collection);
+
+        NftMint {
+            owner_id: &collection.admin,
+            token_ids: &[token_id.to_string()],
+            memo: None,
+        }
+        .emit();
+
+        token_id
+    }
+
+    pub fn collection_of(&self, token_id: u64) -> Option<CollectionId> {
+        self.token_collection.get(&token_id)
+    }
+
+    /// Updates the metadata of a token minted into a collection. Only the
+    /// collection admin may call this, and only when the collection was
+    /// created with `UNLOCKED_METADATA`.
+    pub fn update_token_metadata(&mut self, token_id: u64, metadata: TokenMetadata) {
+        let collection_id = match self.token_collection.get(&token_id) {
+            Some(collection_id) => collection_id,
+            None => env::panic(b"Token does not belong to a collection"),
+        };
+        let collection = match self.collections.get(&collection_id) {
+            Some(collection) => collection,
+            None => env::panic(b"Collection not found"),
+        };
+        if env::predecessor_account_id() != collection.admin {
+            env::panic(b"Only the collection admin can update token metadata.");
+        }
+        if !collection.settings.contains(CollectionSettings::UNLOCKED_METADATA) {
+            env::panic(b"This collection's token metadata is locked.");
+        }
+
+        let mut nft = match self.nfts.get(&token_id) {
+            Some(nft) => nft,
+            None => env::panic(b"Token not found"),
+        };
+        nft.metadata = metadata;
+        self.nfts.insert(&token_id, &nft);
+    }
+
+    /// Lists the ids of tokens belonging to `collection_id`, paginated like
+    /// the rest of the enumeration methods.
+    pub fn tokens_in_collection(&self, collection_id: CollectionId, from_index: u64, limit: u64) -> Vec<u64> {
+        self.token_ids
+            .iter()
+            .filter(|token_id| self.token_collection.get(token_id) == Some(collection_id))
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+}
@@ -0,0 +1,69 @@
+//! NEP-181 style enumeration plus the internal per-owner index that backs it.
+//!
+//! `self.nfts` is keyed by the real `token_id` (a `LookupMap`); these helpers
+//! keep the per-owner index in step with it on mint, claim, transfer and burn.
+//! `migrate` in `upgrade.rs` builds the same index directly.
+
+use near_sdk::collections::UnorderedSet;
+use near_sdk::{near_bindgen, AccountId};
+
+use crate::{NFTContract, NFTContractContract, NFT};
+
+impl NFTContract {
+    pub(crate) fn internal_add_token_to_owner(&mut self, owner_id: &AccountId, token_id: u64) {
+        let mut tokens = self.tokens_per_owner.get(owner_id).unwrap_or_else(|| {
+            UnorderedSet::new(format!("o{}", owner_id).into_bytes())
+        });
+        tokens.insert(&token_id);
+        self.tokens_per_owner.insert(owner_id, &tokens);
+    }
+
+    pub(crate) fn internal_remove_token_from_owner(&mut self, owner_id: &AccountId, token_id: u64) {
+        if let Some(mut tokens) = self.tokens_per_owner.get(owner_id) {
+            tokens.remove(&token_id);
+            if tokens.is_empty() {
+                self.tokens_per_owner.remove(owner_id);
+            } else {
+                self.tokens_per_owner.insert(owner_id, &tokens);
+            }
+        }
+        // A change of owner invalidates any use-authorities the previous
+        // owner delegated, the same way NEP-178 approvals reset on transfer.
+        self.use_authorities.remove(&token_id);
+    }
+}
+
+#[near_bindgen]
+impl NFTContract {
+    pub fn nft_total_supply(&self) -> u64 {
+        self.token_ids.len()
+    }
+
+    pub fn nft_tokens(&self, from_index: u64, limit: u64) -> Vec<NFT> {
+        self.token_ids
+            .iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .map(|token_id| self.nfts.get(&token_id).expect("Token ids and tokens out of sync"))
+            .collect()
+    }
+
+    pub fn nft_supply_for_owner(&self, account_id: AccountId) -> u64 {
+        self.tokens_per_owner
+            .get(&account_id)
+            .map(|tokens| tokens.len())
+            .unwrap_or(0)
+    }
+
+    pub fn nft_tokens_for_owner(&self, account_id: AccountId, from_index: u64, limit: u64) -> Vec<NFT> {
+        match self.tokens_per_owner.get(&account_id) {
+            Some(tokens) => tokens
+                .iter()
+                .skip(from_index as usize)
+                .take(limit as usize)
+                .map(|token_id| self.nfts.get(&token_id).expect("Token ids and tokens out of sync"))
+                .collect(),
+            None => vec![],
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! NEP-297 structured events for indexers.
+//!
+//! Each event is logged as a single line prefixed with `EVENT_JSON:` so that
+//! indexers can pick activity out of the receipt logs without having to know
+//! about `NFTContract`'s internal layout.
+
+use near_sdk::env;
+use near_sdk::serde::Serialize;
+use near_sdk::serde_json;
+
+const STANDARD: &str = "nep171";
+const VERSION: &str = "1.0.0";
+
+fn emit_event<T: Serialize>(event: &str, data: &[T]) {
+    let envelope = serde_json::json!({
+        "standard": STANDARD,
+        "version": VERSION,
+        "event": event,
+        "data": data,
+    });
+    env::log(format!("EVENT_JSON:{}", envelope).as_bytes());
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftMint<'a> {
+    pub owner_id: &'a str,
+    pub token_ids: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<&'a str>,
+}
+
+impl NftMint<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[NftMint<'_>]) {
+        emit_event("nft_mint", data);
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftTransfer<'a> {
+    pub old_owner_id: &'a str,
+    pub new_owner_id: &'a str,
+    pub token_ids: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<&'a str>,
+}
+
+impl NftTransfer<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[NftTransfer<'_>]) {
+        emit_event("nft_transfer", data);
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftBurn<'a> {
+    pub owner_id: &'a str,
+    pub token_ids: &'a [String],
+}
+
+impl NftBurn<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[NftBurn<'_>]) {
+        emit_event("nft_burn", data);
+    }
+}
@@ -1,44 +1,126 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::Vector;
-use near_sdk::{env, near_bindgen, AccountId, PanicOnDefault};
+use near_sdk::collections::{LookupMap, UnorderedSet};
+use near_sdk::serde::Serialize;
+use near_sdk::{env, ext_contract, near_bindgen, AccountId, Balance, Gas, PanicOnDefault, Promise, PromiseResult};
 use std::collections::HashSet;
 
+mod collection;
+mod enumeration;
+mod events;
+mod metadata;
+mod owner;
+mod upgrade;
+mod uses;
+use collection::{Collection, CollectionId, CollectionSettings};
+use events::{NftMint, NftTransfer};
+use metadata::{NFTContractMetadata, TokenMetadata};
+use uses::Uses;
+
+pub(crate) const NO_DEPOSIT: Balance = 0;
+const GAS_FOR_NFT_ON_TRANSFER: Gas = 25_000_000_000_000;
+const GAS_FOR_RESOLVE_TRANSFER: Gas = 10_000_000_000_000;
+
+fn assert_one_yocto() {
+    assert_eq!(
+        env::attached_deposit(),
+        1,
+        "Requires attached deposit of exactly 1 yoctoNEAR"
+    );
+}
+
 // Define the NFT structure
-#[derive(BorshSerialize, BorshDeserialize)]
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
 pub struct NFT {
     pub owner_id: AccountId,
     pub token_id: u64,
-    pub image_url: String,
+    pub metadata: TokenMetadata,
+    pub uses: Option<Uses>,
+}
+
+#[ext_contract(ext_nft_receiver)]
+trait NFTReceiver {
+    fn nft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        previous_owner_id: AccountId,
+        token_id: u64,
+        msg: String,
+    ) -> bool;
+}
+
+#[ext_contract(ext_self)]
+trait NFTResolver {
+    fn nft_resolve_transfer(
+        &mut self,
+        previous_owner_id: AccountId,
+        receiver_id: AccountId,
+        token_id: u64,
+    ) -> bool;
 }
 
 // Define the contract state
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct NFTContract {
-    pub nfts: Vector<NFT>,
+    pub nfts: LookupMap<u64, NFT>,
+    pub token_ids: UnorderedSet<u64>,
+    pub tokens_per_owner: LookupMap<AccountId, UnorderedSet<u64>>,
     pub owner_id: AccountId,
     pub next_public_token_id: u64,
     pub allowed_claimers: HashSet<AccountId>,
+    pub metadata: NFTContractMetadata,
+    /// Metadata blueprint for the tokens created by `claim`, set by the most
+    /// recent `mint` call so claimers inherit what the owner attached.
+    pub claimable_metadata: Option<TokenMetadata>,
+    /// Uses blueprint for the tokens created by `claim`, mirroring
+    /// `claimable_metadata`.
+    pub claimable_uses: Option<Uses>,
+    pub pending_owner: Option<AccountId>,
+    pub use_authorities: LookupMap<u64, HashSet<AccountId>>,
+    pub collections: LookupMap<CollectionId, Collection>,
+    pub next_collection_id: CollectionId,
+    pub token_collection: LookupMap<u64, CollectionId>,
 }
 
 #[near_bindgen]
 impl NFTContract {
     #[init]
-    pub fn new(owner_id: AccountId) -> Self {
+    pub fn new(owner_id: AccountId, metadata: NFTContractMetadata) -> Self {
         Self {
-            nfts: Vector::new(b"n".to_vec()),
+            nfts: LookupMap::new(b"n".to_vec()),
+            token_ids: UnorderedSet::new(b"i".to_vec()),
+            tokens_per_owner: LookupMap::new(b"o".to_vec()),
             owner_id,
             next_public_token_id: 1,
             allowed_claimers: HashSet::new(),
+            metadata,
+            claimable_metadata: None,
+            claimable_uses: None,
+            pending_owner: None,
+            use_authorities: LookupMap::new(b"u".to_vec()),
+            collections: LookupMap::new(b"c".to_vec()),
+            next_collection_id: 0,
+            token_collection: LookupMap::new(b"t".to_vec()),
         }
     }
 
-    pub fn mint(&mut self, token_id: u64, image_url: String, allowed_claimers: Option<Vec<AccountId>>) -> bool {
-    let caller = env::signer_account_id();
-    if caller != self.owner_id {
-        env::panic(b"Only the owner can mint NFTs.");
+    pub fn nft_metadata(&self) -> NFTContractMetadata {
+        self.metadata.clone()
     }
-    
+
+    pub fn mint(
+        &mut self,
+        token_id: u64,
+        metadata: Option<TokenMetadata>,
+        uses: Option<Uses>,
+        allowed_claimers: Option<Vec<AccountId>>,
+    ) -> bool {
+        self.require_owner();
+
+        self.claimable_metadata = metadata.clone();
+        self.claimable_uses = uses.clone();
+
         if let Some(claimers_list) = allowed_claimers {
             // If a list of allowed claimers is provided, add them to the HashSet
             for claimer in claimers_list {
@@ -46,59 +128,226 @@ impl NFTContract {
             }
         } else {
             // If no list is provided, mint 100 NFTs that can be claimed by anyone
-            for i in 0..100 {
-                self.nfts.push(&NFT {
-                    owner_id: self.owner_id.clone(),
-                    token_id: self.next_public_token_id,
-                    image_url: image_url.clone(), 
-                });
+            let mut minted_token_ids = Vec::with_capacity(100);
+            for _ in 0..100 {
+                let token_id = self.next_public_token_id;
+                self.nfts.insert(
+                    &token_id,
+                    &NFT {
+                        owner_id: self.owner_id.clone(),
+                        token_id,
+                        metadata: metadata.clone().unwrap_or_default(),
+                        uses: uses.clone(),
+                    },
+                );
+                self.token_ids.insert(&token_id);
+                self.internal_add_token_to_owner(&self.owner_id.clone(), token_id);
+                minted_token_ids.push(token_id.to_string());
                 self.next_public_token_id += 1;
             }
+            NftMint {
+                owner_id: &self.owner_id,
+                token_ids: &minted_token_ids,
+                memo: None,
+            }
+            .emit();
         }
         true
     }
-    
-    pub fn transfer(&mut self, receiver_id: AccountId, token_id: u64) -> bool {
-        let caller = env::signer_account_id();
-        let mut nft = match self.nfts.get(token_id) {
+
+    /// NEP-171 `nft_transfer`: moves `token_id` to `receiver_id`, persisting the
+    /// mutation back into `self.nfts`. Requires a one-yoctoNEAR deposit so the
+    /// call can only come from a full-access key, per the standard.
+    #[payable]
+    pub fn nft_transfer(&mut self, receiver_id: AccountId, token_id: u64) {
+        assert_one_yocto();
+        let caller = env::predecessor_account_id();
+        let mut nft = match self.nfts.get(&token_id) {
             Some(nft) => nft,
-            None => return false, // NFT with the given token_id doesn't exist
+            None => env::panic(b"Token not found"),
         };
         if nft.owner_id != caller {
-            return false; // Caller is not the owner of the NFT
+            env::panic(b"Caller is not the owner of this NFT");
+        }
+        if let Some(collection_id) = self.token_collection.get(&token_id) {
+            let collection = self
+                .collections
+                .get(&collection_id)
+                .unwrap_or_else(|| env::panic(b"Collection not found"));
+            if !collection.settings.contains(CollectionSettings::TRANSFERABLE) {
+                env::panic(b"This collection's tokens are not transferable.");
+            }
         }
 
-        nft.owner_id = receiver_id;
-        true
+        let old_owner_id = nft.owner_id.clone();
+        nft.owner_id = receiver_id.clone();
+        self.nfts.insert(&token_id, &nft);
+        self.internal_remove_token_from_owner(&old_owner_id, token_id);
+        self.internal_add_token_to_owner(&receiver_id, token_id);
+
+        NftTransfer {
+            old_owner_id: &old_owner_id,
+            new_owner_id: &receiver_id,
+            token_ids: &[token_id.to_string()],
+            authorized_id: None,
+            memo: None,
+        }
+        .emit();
+    }
+
+    /// NEP-171 `nft_transfer_call`: transfers `token_id` to `receiver_id` and
+    /// invokes `nft_on_transfer` on the receiving contract so it can react to
+    /// (or refuse) the gift. `nft_resolve_transfer` reverts the transfer if the
+    /// receiver comes back with `true`.
+    #[payable]
+    pub fn nft_transfer_call(&mut self, receiver_id: AccountId, token_id: u64, msg: String) -> Promise {
+        assert_one_yocto();
+        let caller = env::predecessor_account_id();
+        let mut nft = match self.nfts.get(&token_id) {
+            Some(nft) => nft,
+            None => env::panic(b"Token not found"),
+        };
+        if nft.owner_id != caller {
+            env::panic(b"Caller is not the owner of this NFT");
+        }
+        if let Some(collection_id) = self.token_collection.get(&token_id) {
+            let collection = self
+                .collections
+                .get(&collection_id)
+                .unwrap_or_else(|| env::panic(b"Collection not found"));
+            if !collection.settings.contains(CollectionSettings::TRANSFERABLE) {
+                env::panic(b"This collection's tokens are not transferable.");
+            }
+        }
+
+        let previous_owner_id = nft.owner_id.clone();
+        nft.owner_id = receiver_id.clone();
+        self.nfts.insert(&token_id, &nft);
+        self.internal_remove_token_from_owner(&previous_owner_id, token_id);
+        self.internal_add_token_to_owner(&receiver_id, token_id);
+
+        NftTransfer {
+            old_owner_id: &previous_owner_id,
+            new_owner_id: &receiver_id,
+            token_ids: &[token_id.to_string()],
+            authorized_id: None,
+            memo: None,
+        }
+        .emit();
+
+        ext_nft_receiver::nft_on_transfer(
+            caller,
+            previous_owner_id.clone(),
+            token_id,
+            msg,
+            &receiver_id,
+            NO_DEPOSIT,
+            GAS_FOR_NFT_ON_TRANSFER,
+        )
+        .then(ext_self::nft_resolve_transfer(
+            previous_owner_id,
+            receiver_id,
+            token_id,
+            &env::current_account_id(),
+            NO_DEPOSIT,
+            GAS_FOR_RESOLVE_TRANSFER,
+        ))
     }
-    
+
+    /// Callback for `nft_transfer_call`. If the receiver returned `true`
+    /// (refusing the token), ownership reverts to `previous_owner_id`.
+    #[private]
+    pub fn nft_resolve_transfer(
+        &mut self,
+        previous_owner_id: AccountId,
+        receiver_id: AccountId,
+        token_id: u64,
+    ) -> bool {
+        let should_revert = match env::promise_result(0) {
+            PromiseResult::Successful(value) => {
+                // An ambiguous (non-bool) response from the receiver is treated the
+                // same as a failed promise: revert, so the sender can't lose a gift
+                // to a receiver contract that returns malformed JSON.
+                near_sdk::serde_json::from_slice::<bool>(&value).unwrap_or(true)
+            }
+            _ => true,
+        };
+
+        if !should_revert {
+            return false;
+        }
+
+        if let Some(mut nft) = self.nfts.get(&token_id) {
+            if nft.owner_id == receiver_id {
+                nft.owner_id = previous_owner_id.clone();
+                self.nfts.insert(&token_id, &nft);
+                self.internal_remove_token_from_owner(&receiver_id, token_id);
+                self.internal_add_token_to_owner(&previous_owner_id, token_id);
+                return true;
+            }
+        }
+        false
+    }
+
     pub fn claim(&mut self) -> bool {
         let caller = env::signer_account_id();
         if self.allowed_claimers.is_empty() || self.allowed_claimers.contains(&caller) {
-            self.nfts.push(&NFT {
-                owner_id: caller,
-                token_id: self.next_public_token_id,
-                image_url: "".to_string(), 
-            });
+            let token_id = self.next_public_token_id;
+            self.nfts.insert(
+                &token_id,
+                &NFT {
+                    owner_id: caller.clone(),
+                    token_id,
+                    metadata: self.claimable_metadata.clone().unwrap_or_default(),
+                    uses: self.claimable_uses.clone(),
+                },
+            );
+            self.token_ids.insert(&token_id);
+            self.internal_add_token_to_owner(&caller, token_id);
+            NftMint {
+                owner_id: &caller,
+                token_ids: &[token_id.to_string()],
+                memo: None,
+            }
+            .emit();
             self.next_public_token_id += 1;
             true
         } else {
             false
         }
     }
-    
+
 }
 // ... (code for the contract)
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::uses::UseMethod;
     use near_sdk::test_utils::{accounts, VMContextBuilder};
     use near_sdk::{MockedBlockchain, testing_env};
 
     // Helper function to initialize the contract
     fn init_contract(owner_id: AccountId) -> NFTContract {
-        NFTContract::new(owner_id)
+        NFTContract::new(owner_id, sample_contract_metadata())
+    }
+
+    fn sample_contract_metadata() -> NFTContractMetadata {
+        NFTContractMetadata {
+            spec: "nft-1.0.0".to_string(),
+            name: "Gift NFT".to_string(),
+            symbol: "GIFT".to_string(),
+            icon: None,
+            base_uri: None,
+        }
+    }
+
+    fn sample_token_metadata(title: &str) -> TokenMetadata {
+        TokenMetadata {
+            title: Some(title.to_string()),
+            media: Some("http://example.com/nft1".to_string()),
+            ..Default::default()
+        }
     }
 
     #[test]
@@ -112,15 +361,18 @@ mod tests {
         // Initialize the contract with the owner account ID
         let mut contract = init_contract(accounts(0).to_string());
 
-        // The owner can mint NFTs with allowed claimers list
-        let allowed_claimers = vec![accounts(1).to_string(), accounts(2).to_string()];
-        assert!(contract.mint(1, "http://example.com/nft1".to_string(), Some(allowed_claimers.clone())));
+        // The owner can mint a public batch of NFTs
+        assert!(contract.mint(1, Some(sample_token_metadata("nft1")), None, None));
 
-        // The minted NFT should be added to the contract's nfts vector
-        let nft = contract.nfts.get(0).unwrap();
+        // The minted NFT should be stored under its real token id
+        let nft = contract.nfts.get(&1).unwrap();
         assert_eq!(nft.owner_id, accounts(0).to_string());
         assert_eq!(nft.token_id, 1);
-        assert_eq!(nft.image_url, "http://example.com/nft1");
+        assert_eq!(nft.metadata.title, Some("nft1".to_string()));
+
+        // The owner can also register an allowlist instead of minting directly
+        let allowed_claimers = vec![accounts(1).to_string(), accounts(2).to_string()];
+        assert!(contract.mint(2, None, None, Some(allowed_claimers.clone())));
 
         // Non-owner should not be able to mint NFTs
         let context_non_owner = VMContextBuilder::new()
@@ -128,7 +380,211 @@ mod tests {
             .predecessor_account_id(accounts(1))
             .build(); // Build the VMContext for non-owner account
         testing_env!(context_non_owner);
-        assert_eq!(contract.mint(2, "http://example.com/nft2".to_string(), None), false);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.mint(2, Some(sample_token_metadata("nft2")), None, None);
+        }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_two_step_ownership_transfer() {
+        let context = VMContextBuilder::new()
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(0))
+            .build();
+        testing_env!(context);
+
+        let mut contract = init_contract(accounts(0).to_string());
+        contract.propose_owner(accounts(1).to_string());
+        assert_eq!(contract.get_pending_owner(), Some(accounts(1).to_string()));
+        assert_eq!(contract.get_owner(), accounts(0).to_string());
+
+        // A random account cannot accept on behalf of the pending owner.
+        let context_stranger = VMContextBuilder::new()
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(2))
+            .build();
+        testing_env!(context_stranger);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.accept_owner();
+        }));
+        assert!(result.is_err());
+
+        // The pending owner finalizes the handover.
+        let context_pending = VMContextBuilder::new()
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(1))
+            .build();
+        testing_env!(context_pending);
+        contract.accept_owner();
+        assert_eq!(contract.get_owner(), accounts(1).to_string());
+        assert_eq!(contract.get_pending_owner(), None);
+    }
+
+    #[test]
+    fn test_upgrade_requires_owner() {
+        let context = VMContextBuilder::new()
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(1))
+            .build();
+        testing_env!(context);
+
+        let mut contract = init_contract(accounts(0).to_string());
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.upgrade();
+        }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_use_token_burns_on_exhaustion() {
+        let context = VMContextBuilder::new()
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(0))
+            .attached_deposit(1)
+            .build();
+        testing_env!(context);
+
+        let mut contract = init_contract(accounts(0).to_string());
+        let single_use = Uses {
+            use_method: UseMethod::Burn,
+            total: 1,
+            remaining: 1,
+        };
+        contract.mint(1, Some(sample_token_metadata("voucher")), Some(single_use), None);
+
+        // The owner delegates redemption to a merchant account.
+        contract.approve_use_authority(1, accounts(2).to_string());
+
+        let context_merchant = VMContextBuilder::new()
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(2))
+            .build();
+        testing_env!(context_merchant);
+        contract.use_token(1);
+
+        assert!(contract.nfts.get(&1).is_none());
+
+        // A burned token can no longer be transferred.
+        let context_owner = VMContextBuilder::new()
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(0))
+            .attached_deposit(1)
+            .build();
+        testing_env!(context_owner);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.nft_transfer(accounts(1).to_string(), 1);
+        }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_use_authority_does_not_survive_transfer() {
+        let context = VMContextBuilder::new()
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(0))
+            .attached_deposit(1)
+            .build();
+        testing_env!(context);
+
+        let mut contract = init_contract(accounts(0).to_string());
+        let multi_use = Uses {
+            use_method: UseMethod::Multiple,
+            total: 2,
+            remaining: 2,
+        };
+        contract.mint(1, Some(sample_token_metadata("voucher")), Some(multi_use), None);
+
+        // Owner A delegates redemption to a merchant, then gifts the token to B.
+        contract.approve_use_authority(1, accounts(2).to_string());
+        contract.nft_transfer(accounts(1).to_string(), 1);
+
+        // The merchant's delegation from A must not carry over to B's token.
+        let context_merchant = VMContextBuilder::new()
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(2))
+            .build();
+        testing_env!(context_merchant);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.use_token(1);
+        }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_collection_enforces_max_supply_and_transferability() {
+        let context = VMContextBuilder::new()
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(0))
+            .build();
+        testing_env!(context);
+
+        let mut contract = init_contract(accounts(0).to_string());
+        let collection_id = contract.create_collection(accounts(1).to_string(), CollectionSettings::NONE, Some(1));
+
+        let context_admin = VMContextBuilder::new()
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(1))
+            .build();
+        testing_env!(context_admin);
+        let token_id = contract.mint_into(collection_id, Some(sample_token_metadata("campaign-1")));
+        assert_eq!(contract.collection_of(token_id), Some(collection_id));
+
+        // The collection's max_supply of 1 is already reached.
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.mint_into(collection_id, None);
+        }));
+        assert!(result.is_err());
+
+        // The collection isn't Transferable, so the admin can't move the token.
+        let context_transfer = VMContextBuilder::new()
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(1))
+            .attached_deposit(1)
+            .build();
+        testing_env!(context_transfer);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.nft_transfer(accounts(2).to_string(), token_id);
+        }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_update_token_metadata_requires_unlocked_metadata_flag() {
+        let context = VMContextBuilder::new()
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(0))
+            .build();
+        testing_env!(context);
+
+        let mut contract = init_contract(accounts(0).to_string());
+        let locked_collection =
+            contract.create_collection(accounts(1).to_string(), CollectionSettings::NONE, None);
+        let unlocked_collection = contract.create_collection(
+            accounts(1).to_string(),
+            CollectionSettings::UNLOCKED_METADATA,
+            None,
+        );
+
+        let context_admin = VMContextBuilder::new()
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(1))
+            .build();
+        testing_env!(context_admin);
+        let locked_token = contract.mint_into(locked_collection, Some(sample_token_metadata("v1")));
+        let unlocked_token =
+            contract.mint_into(unlocked_collection, Some(sample_token_metadata("v1")));
+
+        // The collection wasn't created with UNLOCKED_METADATA, so updates are rejected.
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.update_token_metadata(locked_token, sample_token_metadata("v2"));
+        }));
+        assert!(result.is_err());
+
+        // UNLOCKED_METADATA lets the collection admin revise a token's metadata.
+        contract.update_token_metadata(unlocked_token, sample_token_metadata("v2"));
+        let nft = contract.nfts.get(&unlocked_token).unwrap();
+        assert_eq!(nft.metadata.title, Some("v2".to_string()));
     }
 
     #[test]
@@ -152,13 +608,142 @@ mod tests {
 
         // Allowed claimer should be able to claim NFTs
         let allowed_claimers = vec![accounts(3).to_string()];
-        contract.mint(1, "http://example.com/nft1".to_string(), Some(allowed_claimers));
+        testing_env!(VMContextBuilder::new()
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(0))
+            .build());
+        contract.mint(1, Some(sample_token_metadata("nft1")), None, Some(allowed_claimers));
+        testing_env!(VMContextBuilder::new()
+            .current_account_id(accounts(3))
+            .signer_account_id(accounts(3))
+            .predecessor_account_id(accounts(3))
+            .build());
         assert!(contract.claim());
 
-        // The claimed NFT should be added to the contract's nfts vector
-        let nft = contract.nfts.get(0).unwrap();
+        // The claimed NFT should inherit the metadata attached at mint time
+        let nft = contract.nfts.get(&1).unwrap();
         assert_eq!(nft.owner_id, accounts(3).to_string());
-        assert_eq!(nft.token_id, 2); // Next token ID after the minted ones
-        assert_eq!(nft.image_url, "");
+        assert_eq!(nft.token_id, 1); // The allowlist-only mint never minted a token itself
+        assert_eq!(nft.metadata.title, Some("nft1".to_string()));
+    }
+
+    #[test]
+    fn test_nft_transfer() {
+        let context = VMContextBuilder::new()
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(0))
+            .attached_deposit(1)
+            .build();
+        testing_env!(context);
+
+        let mut contract = init_contract(accounts(0).to_string());
+        contract.mint(1, Some(sample_token_metadata("nft1")), None, None);
+
+        // Owner transfers token 1 to accounts(1); the mutation must persist.
+        contract.nft_transfer(accounts(1).to_string(), 1);
+        let nft = contract.nfts.get(&1).unwrap();
+        assert_eq!(nft.owner_id, accounts(1).to_string());
+
+        // The original owner can no longer transfer it.
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            contract.nft_transfer(accounts(2).to_string(), 1);
+        }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_nft_resolve_transfer_reverts_on_refusal() {
+        let context = VMContextBuilder::new()
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(0))
+            .attached_deposit(1)
+            .build();
+        testing_env!(context);
+
+        let mut contract = init_contract(accounts(0).to_string());
+        contract.mint(1, Some(sample_token_metadata("nft1")), None, None);
+        contract.nft_transfer_call(accounts(1).to_string(), 1, "".to_string());
+
+        // nft_transfer_call moves ownership optimistically, before the receiver
+        // has had a chance to respond.
+        assert_eq!(contract.nfts.get(&1).unwrap().owner_id, accounts(1).to_string());
+
+        // The receiver refuses the token (`nft_on_transfer` returned `true`), so
+        // resolving the transfer must revert ownership and the owner index.
+        testing_env!(
+            VMContextBuilder::new()
+                .current_account_id(accounts(0))
+                .predecessor_account_id(accounts(0))
+                .build(),
+            near_sdk::VMConfig::test(),
+            near_sdk::RuntimeFeesConfig::test(),
+            vec![PromiseResult::Successful(b"true".to_vec())]
+        );
+        let reverted =
+            contract.nft_resolve_transfer(accounts(0).to_string(), accounts(1).to_string(), 1);
+        assert!(reverted);
+        assert_eq!(contract.nfts.get(&1).unwrap().owner_id, accounts(0).to_string());
+        assert_eq!(contract.nft_tokens_for_owner(accounts(1).to_string(), 0, 10).len(), 0);
+        assert_eq!(contract.nft_tokens_for_owner(accounts(0).to_string(), 0, 10).len(), 1);
+    }
+
+    #[test]
+    fn test_nft_resolve_transfer_keeps_transfer_on_acceptance() {
+        let context = VMContextBuilder::new()
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(0))
+            .attached_deposit(1)
+            .build();
+        testing_env!(context);
+
+        let mut contract = init_contract(accounts(0).to_string());
+        contract.mint(1, Some(sample_token_metadata("nft1")), None, None);
+        contract.nft_transfer_call(accounts(1).to_string(), 1, "".to_string());
+
+        // The receiver accepts the token (`nft_on_transfer` returned `false`),
+        // so resolving the transfer leaves ownership with the receiver.
+        testing_env!(
+            VMContextBuilder::new()
+                .current_account_id(accounts(0))
+                .predecessor_account_id(accounts(0))
+                .build(),
+            near_sdk::VMConfig::test(),
+            near_sdk::RuntimeFeesConfig::test(),
+            vec![PromiseResult::Successful(b"false".to_vec())]
+        );
+        let reverted =
+            contract.nft_resolve_transfer(accounts(0).to_string(), accounts(1).to_string(), 1);
+        assert!(!reverted);
+        assert_eq!(contract.nfts.get(&1).unwrap().owner_id, accounts(1).to_string());
+    }
+
+    #[test]
+    fn test_nft_resolve_transfer_reverts_on_ambiguous_response() {
+        let context = VMContextBuilder::new()
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(0))
+            .attached_deposit(1)
+            .build();
+        testing_env!(context);
+
+        let mut contract = init_contract(accounts(0).to_string());
+        contract.mint(1, Some(sample_token_metadata("nft1")), None, None);
+        contract.nft_transfer_call(accounts(1).to_string(), 1, "".to_string());
+
+        // A receiver that returns malformed/non-bool JSON on a successful
+        // promise must not be able to keep the token by accident.
+        testing_env!(
+            VMContextBuilder::new()
+                .current_account_id(accounts(0))
+                .predecessor_account_id(accounts(0))
+                .build(),
+            near_sdk::VMConfig::test(),
+            near_sdk::RuntimeFeesConfig::test(),
+            vec![PromiseResult::Successful(b"not-a-bool".to_vec())]
+        );
+        let reverted =
+            contract.nft_resolve_transfer(accounts(0).to_string(), accounts(1).to_string(), 1);
+        assert!(reverted);
+        assert_eq!(contract.nfts.get(&1).unwrap().owner_id, accounts(0).to_string());
     }
 }
@@ -0,0 +1,52 @@
+//! Contract ownership: a single privileged `owner_id` plus a safe two-step
+//! handover so control can never be transferred to a typo'd account by accident.
+
+use near_sdk::{env, near_bindgen, AccountId};
+
+use crate::{NFTContract, NFTContractContract};
+
+impl NFTContract {
+    /// Panics unless the caller is the current owner. Use this instead of an
+    /// ad-hoc `caller != self.owner_id` check in every privileged method.
+    pub(crate) fn require_owner(&self) {
+        if env::predecessor_account_id() != self.owner_id {
+            env::panic(b"Only the owner can call this method.");
+        }
+    }
+}
+
+#[near_bindgen]
+impl NFTContract {
+    /// Starts a handover: `new_owner` must call `accept_owner` to finalize it.
+    pub fn propose_owner(&mut self, new_owner: AccountId) {
+        self.require_owner();
+        self.pending_owner = Some(new_owner);
+    }
+
+    /// Finalizes a handover started by `propose_owner`. Only the proposed
+    /// account can call this.
+    pub fn accept_owner(&mut self) {
+        let caller = env::predecessor_account_id();
+        match &self.pending_owner {
+            Some(pending) if pending == &caller => {
+                self.owner_id = caller;
+                self.pending_owner = None;
+            }
+            _ => env::panic(b"Caller is not the pending owner."),
+        }
+    }
+
+    /// Cancels a pending handover without changing the current owner.
+    pub fn renounce_owner(&mut self) {
+        self.require_owner();
+        self.pending_owner = None;
+    }
+
+    pub fn get_owner(&self) -> AccountId {
+        self.owner_id.clone()
+    }
+
+    pub fn get_pending_owner(&self) -> Option<AccountId> {
+        self.pending_owner.clone()
+    }
+}
@@ -0,0 +1,122 @@
+//! Owner-gated contract upgrades.
+//!
+//! `upgrade` redeploys the contract's own account with the Wasm passed as
+//! input and schedules a `migrate` call with the gas that is left after
+//! holding back `GAS_FOR_UPGRADE_CALL` for the upgrade call itself, so a new
+//! `NFTContract` layout can read and convert the previous Borsh state instead
+//! of failing to deserialize it.
+
+use near_sdk::borsh::BorshDeserialize;
+use near_sdk::collections::{LookupMap, UnorderedSet, Vector};
+use near_sdk::{env, near_bindgen, AccountId, Gas, Promise};
+use std::collections::HashSet;
+
+use crate::metadata::{NFTContractMetadata, TokenMetadata};
+use crate::{NFTContract, NFTContractContract, NFT, NO_DEPOSIT};
+
+const GAS_FOR_UPGRADE_CALL: Gas = 10_000_000_000_000;
+
+/// The pre-upgrade on-chain layout, kept only long enough for `migrate` to
+/// read it back and backfill the fields that didn't exist yet.
+#[derive(BorshDeserialize)]
+pub struct OldNFT {
+    pub owner_id: AccountId,
+    pub token_id: u64,
+    pub image_url: String,
+}
+
+#[derive(BorshDeserialize)]
+pub struct OldNFTContract {
+    pub nfts: Vector<OldNFT>,
+    pub owner_id: AccountId,
+    pub next_public_token_id: u64,
+    pub allowed_claimers: HashSet<AccountId>,
+}
+
+#[near_bindgen]
+impl NFTContract {
+    /// Deploys `env::input()` as this account's new code, then calls
+    /// `migrate` on it with the remaining gas less `GAS_FOR_UPGRADE_CALL`.
+    pub fn upgrade(&mut self) -> Promise {
+        self.require_owner();
+        let code = match env::input() {
+            Some(code) => code,
+            None => env::panic(b"Missing Wasm payload"),
+        };
+
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call(
+                b"migrate".to_vec(),
+                Vec::new(),
+                NO_DEPOSIT,
+                env::prepaid_gas() - env::used_gas() - GAS_FOR_UPGRADE_CALL,
+            )
+    }
+
+    /// Reconstructs the current contract layout from the pre-upgrade state,
+    /// backfilling `TokenMetadata` for tokens minted before NEP-177 support.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let old: OldNFTContract =
+            env::state_read().unwrap_or_else(|| env::panic(b"Failed to read old state"));
+
+        let mut nfts = LookupMap::new(b"n".to_vec());
+        let mut token_ids = UnorderedSet::new(b"i".to_vec());
+        let mut tokens_per_owner: LookupMap<AccountId, UnorderedSet<u64>> =
+            LookupMap::new(b"o".to_vec());
+        // Read every old entry before writing any: the old `Vector` and the new
+        // `LookupMap` share the "n" prefix, so a new token's key can be the key
+        // of an old entry that has not been read yet.
+        let old_nfts: Vec<OldNFT> = old.nfts.iter().collect();
+        for old_nft in old_nfts {
+            let token_id = old_nft.token_id;
+            nfts.insert(
+                &token_id,
+                &NFT {
+                    owner_id: old_nft.owner_id.clone(),
+                    token_id,
+                    metadata: TokenMetadata {
+                        media: if old_nft.image_url.is_empty() {
+                            None
+                        } else {
+                            Some(old_nft.image_url)
+                        },
+                        ..Default::default()
+                    },
+                    uses: None,
+                },
+            );
+            token_ids.insert(&token_id);
+            let mut owner_tokens = tokens_per_owner
+                .get(&old_nft.owner_id)
+                .unwrap_or_else(|| UnorderedSet::new(format!("o{}", old_nft.owner_id).into_bytes()));
+            owner_tokens.insert(&token_id);
+            tokens_per_owner.insert(&old_nft.owner_id, &owner_tokens);
+        }
+
+        Self {
+            nfts,
+            token_ids,
+            tokens_per_owner,
+            owner_id: old.owner_id,
+            next_public_token_id: old.next_public_token_id,
+            allowed_claimers: old.allowed_claimers,
+            metadata: NFTContractMetadata {
+                spec: "nft-1.0.0".to_string(),
+                name: "Gift NFT".to_string(),
+                symbol: "GIFT".to_string(),
+                icon: None,
+                base_uri: None,
+            },
+            claimable_metadata: None,
+            claimable_uses: None,
+            pending_owner: None,
+            use_authorities: LookupMap::new(b"u".to_vec()),
+            collections: LookupMap::new(b"c".to_vec()),
+            next_collection_id: 0,
+            token_collection: LookupMap::new(b"t".to_vec()),
+        }
+    }
+}
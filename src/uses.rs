@@ -0,0 +1,110 @@
+//! Limited-use NFTs: gift/coupon tokens that can be redeemed a fixed number
+//! of times, optionally by someone other than the owner.
+
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, AccountId};
+
+use crate::events::NftBurn;
+use crate::{NFTContract, NFTContractContract};
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum UseMethod {
+    /// The token can be used exactly once and is left in place afterwards.
+    Single,
+    /// The token can be used up to `total` times and is left in place.
+    Multiple,
+    /// The token is burned once `remaining` reaches zero.
+    Burn,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Uses {
+    pub use_method: UseMethod,
+    pub total: u64,
+    pub remaining: u64,
+}
+
+#[near_bindgen]
+impl NFTContract {
+    /// Lets `authority` redeem `token_id` on the owner's behalf, e.g. a
+    /// merchant redeeming a gifted voucher without owning it.
+    pub fn approve_use_authority(&mut self, token_id: u64, authority: AccountId) {
+        let nft = match self.nfts.get(&token_id) {
+            Some(nft) => nft,
+            None => env::panic(b"Token not found"),
+        };
+        if nft.owner_id != env::predecessor_account_id() {
+            env::panic(b"Only the token owner can approve use authorities.");
+        }
+
+        let mut authorities = self.use_authorities.get(&token_id).unwrap_or_default();
+        authorities.insert(authority);
+        self.use_authorities.insert(&token_id, &authorities);
+    }
+
+    pub fn revoke_use_authority(&mut self, token_id: u64, authority: AccountId) {
+        let nft = match self.nfts.get(&token_id) {
+            Some(nft) => nft,
+            None => env::panic(b"Token not found"),
+        };
+        if nft.owner_id != env::predecessor_account_id() {
+            env::panic(b"Only the token owner can revoke use authorities.");
+        }
+
+        if let Some(mut authorities) = self.use_authorities.get(&token_id) {
+            authorities.remove(&authority);
+            self.use_authorities.insert(&token_id, &authorities);
+        }
+    }
+
+    /// Redeems one use of `token_id`. Burns the token once a `Burn`-method
+    /// token's uses are exhausted.
+    pub fn use_token(&mut self, token_id: u64) {
+        let mut nft = match self.nfts.get(&token_id) {
+            Some(nft) => nft,
+            None => env::panic(b"Token not found"),
+        };
+
+        let caller = env::predecessor_account_id();
+        let is_authorized = nft.owner_id == caller
+            || self
+                .use_authorities
+                .get(&token_id)
+                .map_or(false, |authorities| authorities.contains(&caller));
+        if !is_authorized {
+            env::panic(b"Caller is not the owner or an approved use authority.");
+        }
+
+        let mut uses = match nft.uses.clone() {
+            Some(uses) => uses,
+            None => env::panic(b"This token has no limited uses."),
+        };
+        if uses.remaining == 0 {
+            env::panic(b"This token has no uses remaining.");
+        }
+
+        uses.remaining -= 1;
+        if uses.use_method == UseMethod::Single {
+            uses.remaining = 0;
+        }
+        let exhausted = uses.remaining == 0;
+
+        if exhausted && uses.use_method == UseMethod::Burn {
+            self.nfts.remove(&token_id);
+            self.token_ids.remove(&token_id);
+            self.use_authorities.remove(&token_id);
+            self.internal_remove_token_from_owner(&nft.owner_id, token_id);
+            NftBurn {
+                owner_id: &nft.owner_id,
+                token_ids: &[token_id.to_string()],
+            }
+            .emit();
+        } else {
+            nft.uses = Some(uses);
+            self.nfts.insert(&token_id, &nft);
+        }
+    }
+}